@@ -4,6 +4,15 @@ use std::{
     num::NonZero,
 };
 
+mod tag;
+pub use tag::{ParseError, VlanTag, TPID_CUSTOMER, TPID_SERVICE, TPID_SERVICE_LEGACY};
+
+mod stack;
+pub use stack::{VlanStack, DEFAULT_MAX_DEPTH};
+
+mod set;
+pub use set::{VlanSet, VlanSetIter, MAX_MEMBERS};
+
 pub type RawVlanId = u16;
 
 /// Types that can be converted to a raw VLAN ID (u16).
@@ -323,6 +332,84 @@ impl Hash for MaybeVlanId {
     }
 }
 
+/// The IEEE 802.1Q Tag Control Information (TCI) field.
+///
+/// This is the 16-bit field carried immediately after the TPID in an
+/// 802.1Q tag: bits 15-13 are the Priority Code Point (PCP), bit 12 is
+/// the Drop Eligible Indicator (DEI, formerly CFI), and bits 11-0 are
+/// the VID.
+///
+/// It has the same memory layout as `u16`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct TagControlInformation {
+    inner: u16,
+}
+
+impl TagControlInformation {
+    const PCP_SHIFT: u32 = 13;
+    const DEI_SHIFT: u32 = 12;
+    const VID_MASK: u16 = 0x0FFF;
+
+    /// Build a TCI from its constituent parts.
+    ///
+    /// # Panics (debug only)
+    ///
+    /// Panics in debug builds if `pcp > 7`; in release builds the extra
+    /// bits are silently truncated.
+    pub fn new(pcp: u8, dei: bool, vid: MaybeVlanId) -> Self {
+        debug_assert!(pcp <= 0b111, "PCP out of range: {pcp} (must be 0..=7)");
+        let inner = ((pcp as u16 & 0b111) << Self::PCP_SHIFT)
+            | ((dei as u16) << Self::DEI_SHIFT)
+            | vid.as_raw_vlan_id();
+        Self { inner }
+    }
+
+    /// Priority Code Point (0..=7)
+    pub const fn pcp(&self) -> u8 {
+        (self.inner >> Self::PCP_SHIFT) as u8 & 0b111
+    }
+
+    /// Drop Eligible Indicator (formerly CFI)
+    pub const fn dei(&self) -> bool {
+        (self.inner >> Self::DEI_SHIFT) & 1 != 0
+    }
+
+    /// VLAN ID carried by this tag
+    pub fn vid(&self) -> MaybeVlanId {
+        MaybeVlanId::try_new(self.inner & Self::VID_MASK)
+            .expect("TCI invariant: VID bits are validated on construction")
+    }
+
+    /// Parse a TCI from its big-endian on-wire representation.
+    pub fn from_be_bytes(bytes: [u8; 2]) -> Result<Self, InvalidVlanId> {
+        let inner = u16::from_be_bytes(bytes);
+        MaybeVlanId::try_new(inner & Self::VID_MASK)?;
+        Ok(Self { inner })
+    }
+
+    /// Pack this TCI into its big-endian on-wire representation.
+    pub const fn to_be_bytes(&self) -> [u8; 2] {
+        self.inner.to_be_bytes()
+    }
+}
+
+impl Debug for TagControlInformation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TagControlInformation")
+            .field("pcp", &self.pcp())
+            .field("dei", &self.dei())
+            .field("vid", &self.vid())
+            .finish()
+    }
+}
+
+impl AsRawVlanId for TagControlInformation {
+    fn as_raw_vlan_id(&self) -> RawVlanId {
+        self.inner & Self::VID_MASK
+    }
+}
+
 #[cfg(feature = "serde")]
 mod serialization {
     use super::*;
@@ -330,12 +417,19 @@ mod serialization {
     use serde::ser::{Serialize, Serializer};
     use std::fmt;
 
+    // `VlanId`/`MaybeVlanId` serialize as a plain `u16` in compact/binary
+    // formats, and as a decimal string in human-readable ones (JSON, etc.),
+    // so they read naturally in config files yet stay compact on the wire.
+    // `visit_u16`/`visit_u64`/`visit_str` cover the u16, u64 and string
+    // representations a `Deserializer` might hand back regardless of which
+    // one `serialize` chose.
+
     struct VlanIdVisitor;
 
     impl<'de> Visitor<'de> for VlanIdVisitor {
         type Value = VlanId;
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            formatter.write_str("Expected a valid tagged VLAN ID")
+            formatter.write_str("a valid tagged VLAN ID (1-4094), as a number or a string")
         }
 
         fn visit_u16<E>(self, v: u16) -> Result<Self::Value, E>
@@ -344,6 +438,30 @@ mod serialization {
         {
             v.try_into().map_err(|e| de::Error::custom(e))
         }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            u16::try_from(v)
+                .map_err(|_| de::Error::custom(InvalidVlanId))
+                .and_then(|v| self.visit_u16(v))
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let v: u16 = v.parse().map_err(|_| de::Error::custom(InvalidVlanId))?;
+            self.visit_u16(v)
+        }
+
+        fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.visit_str(v)
+        }
     }
 
     impl<'de> Deserialize<'de> for VlanId {
@@ -351,7 +469,11 @@ mod serialization {
         where
             D: Deserializer<'de>,
         {
-            deserializer.deserialize_str(VlanIdVisitor)
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(VlanIdVisitor)
+            } else {
+                deserializer.deserialize_u16(VlanIdVisitor)
+            }
         }
     }
 
@@ -360,7 +482,11 @@ mod serialization {
         where
             S: Serializer,
         {
-            serializer.serialize_u16(self.as_u16())
+            if serializer.is_human_readable() {
+                serializer.collect_str(&self.as_u16())
+            } else {
+                serializer.serialize_u16(self.as_u16())
+            }
         }
     }
 
@@ -369,7 +495,7 @@ mod serialization {
     impl<'de> Visitor<'de> for MaybeVlanIdVisitor {
         type Value = MaybeVlanId;
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            formatter.write_str("Expected a valid tagged VLAN ID or zero (native VLAN)")
+            formatter.write_str("a valid tagged VLAN ID or zero (native VLAN), as a number or a string")
         }
 
         fn visit_u16<E>(self, v: u16) -> Result<Self::Value, E>
@@ -378,6 +504,30 @@ mod serialization {
         {
             v.try_into().map_err(|e| de::Error::custom(e))
         }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            u16::try_from(v)
+                .map_err(|_| de::Error::custom(InvalidVlanId))
+                .and_then(|v| self.visit_u16(v))
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let v: u16 = v.parse().map_err(|_| de::Error::custom(InvalidVlanId))?;
+            self.visit_u16(v)
+        }
+
+        fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.visit_str(v)
+        }
     }
 
     impl<'de> Deserialize<'de> for MaybeVlanId {
@@ -385,7 +535,11 @@ mod serialization {
         where
             D: Deserializer<'de>,
         {
-            deserializer.deserialize_str(MaybeVlanIdVisitor)
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(MaybeVlanIdVisitor)
+            } else {
+                deserializer.deserialize_u16(MaybeVlanIdVisitor)
+            }
         }
     }
 
@@ -394,7 +548,131 @@ mod serialization {
         where
             S: Serializer,
         {
-            serializer.serialize_u16(self.as_u16())
+            if serializer.is_human_readable() {
+                serializer.collect_str(&self.as_u16())
+            } else {
+                serializer.serialize_u16(self.as_u16())
+            }
+        }
+    }
+
+    struct NativeVlanIdVisitor;
+
+    impl<'de> Visitor<'de> for NativeVlanIdVisitor {
+        type Value = NativeVlanId;
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("zero (the native VLAN), as a number or a string")
+        }
+
+        fn visit_u16<E>(self, v: u16) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            v.try_into().map_err(|e| de::Error::custom(e))
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            u16::try_from(v)
+                .map_err(|_| de::Error::custom(InvalidVlanId))
+                .and_then(|v| self.visit_u16(v))
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let v: u16 = v.parse().map_err(|_| de::Error::custom(InvalidVlanId))?;
+            self.visit_u16(v)
+        }
+
+        fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.visit_str(v)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for NativeVlanId {
+        fn deserialize<D>(deserializer: D) -> Result<NativeVlanId, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(NativeVlanIdVisitor)
+            } else {
+                deserializer.deserialize_u16(NativeVlanIdVisitor)
+            }
+        }
+    }
+
+    impl Serialize for NativeVlanId {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if serializer.is_human_readable() {
+                serializer.collect_str(&Self::VALUE)
+            } else {
+                serializer.serialize_u16(Self::VALUE)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::collections::BTreeMap;
+
+        #[test]
+        fn human_readable_round_trip() {
+            let vlan = VlanId::try_new(42).unwrap();
+            let json = serde_json::to_string(&vlan).unwrap();
+            assert_eq!(json, "\"42\"");
+            assert_eq!(serde_json::from_str::<VlanId>(&json).unwrap(), vlan);
+
+            let maybe = MaybeVlanId::NATIVE;
+            let json = serde_json::to_string(&maybe).unwrap();
+            assert_eq!(json, "\"0\"");
+            assert_eq!(serde_json::from_str::<MaybeVlanId>(&json).unwrap(), maybe);
+
+            let json = serde_json::to_string(&NativeVlanId).unwrap();
+            assert_eq!(json, "\"0\"");
+            assert!(serde_json::from_str::<NativeVlanId>(&json).is_ok());
+        }
+
+        #[test]
+        fn non_human_readable_round_trip() {
+            let vlan = VlanId::try_new(42).unwrap();
+            let bytes = bincode::serialize(&vlan).unwrap();
+            assert_eq!(bytes, 42u16.to_le_bytes());
+            assert_eq!(bincode::deserialize::<VlanId>(&bytes).unwrap(), vlan);
+
+            let maybe = MaybeVlanId::try_new(4094).unwrap();
+            let bytes = bincode::serialize(&maybe).unwrap();
+            assert_eq!(bincode::deserialize::<MaybeVlanId>(&bytes).unwrap(), maybe);
+
+            let bytes = bincode::serialize(&NativeVlanId).unwrap();
+            assert_eq!(
+                bincode::deserialize::<NativeVlanId>(&bytes).unwrap(),
+                NativeVlanId
+            );
+        }
+
+        #[test]
+        fn vlan_id_as_json_map_key() {
+            let mut map = BTreeMap::new();
+            map.insert(VlanId::try_new(7).unwrap(), "eng");
+            map.insert(VlanId::try_new(8).unwrap(), "guest");
+
+            let json = serde_json::to_string(&map).unwrap();
+            assert_eq!(json, r#"{"7":"eng","8":"guest"}"#);
+
+            let back: BTreeMap<VlanId, String> = serde_json::from_str(&json).unwrap();
+            assert_eq!(back.get(&VlanId::try_new(7).unwrap()).unwrap(), "eng");
         }
     }
 }
@@ -429,11 +707,43 @@ mod tests {
     #[test]
     fn mem_compat() {
         let zero: u16 = 0u16;
-        let should_be_zero: u16 = unsafe { std::mem::transmute(MaybeVlanId::NATIVE) };
+        let should_be_zero: u16 = unsafe { std::mem::transmute::<MaybeVlanId, u16>(MaybeVlanId::NATIVE) };
         assert_eq!(zero, should_be_zero);
 
         let a: u16 = 3125u16;
         let b = MaybeVlanId::Tagged(VlanId::try_new(3125u16).unwrap());
-        assert_eq!(a, unsafe { std::mem::transmute(b) });
+        assert_eq!(a, unsafe { std::mem::transmute::<MaybeVlanId, u16>(b) });
+    }
+
+    #[test]
+    fn tci_round_trip() {
+        let tci = TagControlInformation::new(5, true, MaybeVlanId::try_new(100).unwrap());
+        let bytes = tci.to_be_bytes();
+        assert_eq!(TagControlInformation::from_be_bytes(bytes).unwrap(), tci);
+
+        let native = TagControlInformation::new(0, false, MaybeVlanId::NATIVE);
+        assert_eq!(native.to_be_bytes(), [0x00, 0x00]);
+        assert_eq!(
+            TagControlInformation::from_be_bytes([0x00, 0x00]).unwrap(),
+            native
+        );
+
+        // PCP 7, DEI set, VID 4094 (0xFFE)
+        let max = TagControlInformation::new(7, true, MaybeVlanId::MAX_TAGGED_VLAN);
+        assert_eq!(max.to_be_bytes(), [0xFF, 0xFE]);
+    }
+
+    #[test]
+    fn tci_rejects_invalid_vid() {
+        // VID 4095 is reserved and rejected by MaybeVlanId.
+        assert!(TagControlInformation::from_be_bytes([0x0F, 0xFF]).is_err());
+    }
+
+    #[test]
+    fn tci_mem_compat() {
+        let tci = TagControlInformation::new(5, true, MaybeVlanId::try_new(100).unwrap());
+        let raw: u16 = 0xB064;
+        assert_eq!(raw, unsafe { std::mem::transmute::<TagControlInformation, u16>(tci) });
+        assert_eq!(unsafe { std::mem::transmute::<u16, TagControlInformation>(raw) }, tci);
     }
 }
@@ -0,0 +1,464 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::{AsRawVlanId, InvalidVlanId, MaybeVlanId};
+
+const WORDS: usize = 64;
+
+/// Number of bits in a [`VlanSet`]'s bitset, one per 12-bit VID value
+/// (0..=4095). Only 0..=4094 (4095 distinct values, including the native
+/// VLAN) can be members, since VID 4095 is reserved and never
+/// representable.
+pub const MAX_MEMBERS: usize = 4096;
+
+/// A compact, fixed-size set of VLAN memberships, as used to describe the
+/// set of VLANs allowed on a trunk port.
+///
+/// Backed by a 4096-bit bitset, so `insert`/`remove`/`contains` are O(1)
+/// and the set algebra is O(64) word operations, with no allocation.
+/// Iteration and formatting scan all 4096 bits.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VlanSet {
+    bits: [u64; WORDS],
+}
+
+impl VlanSet {
+    /// The size of the packed binary representation, in bytes.
+    pub const BYTE_SIZE: usize = MAX_MEMBERS / 8;
+
+    /// Bit 4095 falls inside the bitset's word array but corresponds to
+    /// VID 4095, which is reserved and never a valid `MaybeVlanId` — it
+    /// must stay clear so iteration never has to yield it.
+    const LAST_WORD_MASK: u64 = u64::MAX >> 1;
+
+    /// The empty set.
+    pub const EMPTY: Self = Self { bits: [0; WORDS] };
+
+    /// The full set, containing every representable VLAN ID.
+    pub const FULL: Self = {
+        let mut bits = [u64::MAX; WORDS];
+        bits[WORDS - 1] = Self::LAST_WORD_MASK;
+        Self { bits }
+    };
+
+    /// Build an empty set.
+    pub const fn new() -> Self {
+        Self::EMPTY
+    }
+
+    /// Whether `raw` names a bit this set can actually hold. `AsRawVlanId`
+    /// is implemented by external types too, so callers of `insert`/
+    /// `remove`/`contains` cannot be trusted to stay within range.
+    const fn is_representable(raw: u16) -> bool {
+        raw as usize <= crate::VlanId::MAX_VALUE as usize
+    }
+
+    const fn word_and_bit(raw: u16) -> (usize, u32) {
+        (raw as usize / 64, raw as u32 % 64)
+    }
+
+    fn contains_raw(&self, raw: u16) -> bool {
+        if !Self::is_representable(raw) {
+            return false;
+        }
+        let (word, bit) = Self::word_and_bit(raw);
+        self.bits[word] & (1u64 << bit) != 0
+    }
+
+    fn insert_raw(&mut self, raw: u16) {
+        if !Self::is_representable(raw) {
+            return;
+        }
+        let (word, bit) = Self::word_and_bit(raw);
+        self.bits[word] |= 1u64 << bit;
+    }
+
+    fn remove_raw(&mut self, raw: u16) {
+        if !Self::is_representable(raw) {
+            return;
+        }
+        let (word, bit) = Self::word_and_bit(raw);
+        self.bits[word] &= !(1u64 << bit);
+    }
+
+    /// Add a VLAN to the set.
+    pub fn insert(&mut self, vlan: impl AsRawVlanId) {
+        self.insert_raw(vlan.as_raw_vlan_id());
+    }
+
+    /// Remove a VLAN from the set.
+    pub fn remove(&mut self, vlan: impl AsRawVlanId) {
+        self.remove_raw(vlan.as_raw_vlan_id());
+    }
+
+    /// Whether `vlan` is a member of the set.
+    pub fn contains(&self, vlan: impl AsRawVlanId) -> bool {
+        self.contains_raw(vlan.as_raw_vlan_id())
+    }
+
+    /// The number of VLANs in the set.
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Whether the set has no members.
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&word| word == 0)
+    }
+
+    /// The set of VLANs in either `self` or `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut out = Self::EMPTY;
+        for i in 0..WORDS {
+            out.bits[i] = self.bits[i] | other.bits[i];
+        }
+        out
+    }
+
+    /// The set of VLANs in both `self` and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut out = Self::EMPTY;
+        for i in 0..WORDS {
+            out.bits[i] = self.bits[i] & other.bits[i];
+        }
+        out
+    }
+
+    /// The set of VLANs in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut out = Self::EMPTY;
+        for i in 0..WORDS {
+            out.bits[i] = self.bits[i] & !other.bits[i];
+        }
+        out
+    }
+
+    /// The set of representable VLANs not in `self`.
+    pub fn complement(&self) -> Self {
+        let mut out = Self::EMPTY;
+        for i in 0..WORDS {
+            out.bits[i] = !self.bits[i];
+        }
+        out.bits[WORDS - 1] &= Self::LAST_WORD_MASK;
+        out
+    }
+
+    /// Iterate over the set's members, in ascending order.
+    pub fn iter(&self) -> VlanSetIter<'_> {
+        VlanSetIter { set: self, next: 0 }
+    }
+
+    /// Parse a set from the conventional compressed range syntax, e.g.
+    /// `"0,1-100,200,4000-4094"`.
+    pub fn parse(s: &str) -> Result<Self, InvalidVlanId> {
+        let mut set = Self::EMPTY;
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(set);
+        }
+        for token in s.split(',') {
+            let token = token.trim();
+            if let Some((start, end)) = token.split_once('-') {
+                let start: u16 = start.trim().parse().map_err(|_| InvalidVlanId)?;
+                let end: u16 = end.trim().parse().map_err(|_| InvalidVlanId)?;
+                MaybeVlanId::try_new(start)?;
+                MaybeVlanId::try_new(end)?;
+                if start > end {
+                    return Err(InvalidVlanId);
+                }
+                for raw in start..=end {
+                    set.insert_raw(raw);
+                }
+            } else {
+                let raw: u16 = token.parse().map_err(|_| InvalidVlanId)?;
+                MaybeVlanId::try_new(raw)?;
+                set.insert_raw(raw);
+            }
+        }
+        Ok(set)
+    }
+
+    /// Pack this set into its binary representation: 4096 bits, one per
+    /// 12-bit VID value (bit 4095 is always clear), little-endian within
+    /// each 64-bit word.
+    pub fn to_bytes(&self) -> [u8; Self::BYTE_SIZE] {
+        let mut out = [0u8; Self::BYTE_SIZE];
+        for (i, word) in self.bits.iter().enumerate() {
+            out[i * 8..i * 8 + 8].copy_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+
+    /// Unpack a set from its binary representation.
+    ///
+    /// Bit 4095 (VID 4095, reserved) is cleared if set, since it can never
+    /// be a valid member.
+    pub fn from_bytes(bytes: [u8; Self::BYTE_SIZE]) -> Self {
+        let mut bits = [0u64; WORDS];
+        for (i, word) in bits.iter_mut().enumerate() {
+            *word = u64::from_le_bytes(bytes[i * 8..i * 8 + 8].try_into().unwrap());
+        }
+        bits[WORDS - 1] &= Self::LAST_WORD_MASK;
+        Self { bits }
+    }
+}
+
+impl Default for VlanSet {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl FromStr for VlanSet {
+    type Err = InvalidVlanId;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for VlanSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        let mut i = 0usize;
+        while i < MAX_MEMBERS {
+            if self.contains_raw(i as u16) {
+                let start = i;
+                let mut end = i;
+                while end + 1 < MAX_MEMBERS && self.contains_raw((end + 1) as u16) {
+                    end += 1;
+                }
+                if !first {
+                    f.write_str(",")?;
+                }
+                first = false;
+                if start == end {
+                    write!(f, "{start}")?;
+                } else {
+                    write!(f, "{start}-{end}")?;
+                }
+                i = end + 1;
+            } else {
+                i += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for VlanSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VlanSet({self})")
+    }
+}
+
+impl FromIterator<MaybeVlanId> for VlanSet {
+    fn from_iter<I: IntoIterator<Item = MaybeVlanId>>(iter: I) -> Self {
+        let mut set = Self::EMPTY;
+        for vlan in iter {
+            set.insert(vlan);
+        }
+        set
+    }
+}
+
+/// Iterator over the members of a [`VlanSet`], in ascending order.
+pub struct VlanSetIter<'a> {
+    set: &'a VlanSet,
+    next: usize,
+}
+
+impl Iterator for VlanSetIter<'_> {
+    type Item = MaybeVlanId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < MAX_MEMBERS {
+            let raw = self.next as u16;
+            self.next += 1;
+            if self.set.contains_raw(raw) {
+                return Some(MaybeVlanId::try_new(raw).expect("raw is within MAX_MEMBERS"));
+            }
+        }
+        None
+    }
+}
+
+impl<'a> IntoIterator for &'a VlanSet {
+    type Item = MaybeVlanId;
+    type IntoIter = VlanSetIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialization {
+    use super::*;
+    use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+    use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+    impl Serialize for VlanSet {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if serializer.is_human_readable() {
+                serializer.collect_str(self)
+            } else {
+                let bytes = self.to_bytes();
+                let mut tup = serializer.serialize_tuple(bytes.len())?;
+                for byte in &bytes {
+                    tup.serialize_element(byte)?;
+                }
+                tup.end()
+            }
+        }
+    }
+
+    struct VlanSetVisitor;
+
+    impl<'de> Visitor<'de> for VlanSetVisitor {
+        type Value = VlanSet;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                f,
+                "a VLAN set, as a compressed range string or a {}-byte bitset",
+                VlanSet::BYTE_SIZE
+            )
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            v.parse().map_err(de::Error::custom)
+        }
+
+        fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.visit_str(v)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut bytes = [0u8; VlanSet::BYTE_SIZE];
+            for (i, slot) in bytes.iter_mut().enumerate() {
+                *slot = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+            }
+            Ok(VlanSet::from_bytes(bytes))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for VlanSet {
+        fn deserialize<D>(deserializer: D) -> Result<VlanSet, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(VlanSetVisitor)
+            } else {
+                deserializer.deserialize_tuple(VlanSet::BYTE_SIZE, VlanSetVisitor)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AsRawVlanId, VlanId};
+
+    struct OutOfRangeVlanId(u16);
+
+    impl AsRawVlanId for OutOfRangeVlanId {
+        fn as_raw_vlan_id(&self) -> crate::RawVlanId {
+            self.0
+        }
+    }
+
+    #[test]
+    fn ignores_out_of_range_raw_ids_instead_of_panicking() {
+        let mut set = VlanSet::new();
+        set.insert(OutOfRangeVlanId(u16::MAX));
+        assert!(set.is_empty());
+        assert!(!set.contains(OutOfRangeVlanId(u16::MAX)));
+        set.remove(OutOfRangeVlanId(u16::MAX));
+
+        // The reserved VID 4095 is out of range too.
+        set.insert(OutOfRangeVlanId(4095));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn insert_remove_contains() {
+        let mut set = VlanSet::new();
+        assert!(set.is_empty());
+        set.insert(VlanId::try_new(10).unwrap());
+        assert!(set.contains(VlanId::try_new(10).unwrap()));
+        assert_eq!(set.len(), 1);
+        set.remove(VlanId::try_new(10).unwrap());
+        assert!(!set.contains(VlanId::try_new(10).unwrap()));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn set_algebra() {
+        let a: VlanSet = "1-10".parse().unwrap();
+        let b: VlanSet = "5-15".parse().unwrap();
+
+        assert_eq!(a.union(&b), "1-15".parse().unwrap());
+        assert_eq!(a.intersection(&b), "5-10".parse().unwrap());
+        assert_eq!(a.difference(&b), "1-4".parse().unwrap());
+        assert_eq!(a.complement().intersection(&a), VlanSet::EMPTY);
+    }
+
+    #[test]
+    fn parse_and_format_round_trip() {
+        let set: VlanSet = "0,1-100,200,4000-4094".parse().unwrap();
+        assert_eq!(set.to_string(), "0-100,200,4000-4094");
+        assert!(set.contains(MaybeVlanId::NATIVE));
+        assert!(set.contains(VlanId::try_new(50).unwrap()));
+        assert!(!set.contains(VlanId::try_new(150).unwrap()));
+    }
+
+    #[test]
+    fn parse_rejects_out_of_range() {
+        assert!(VlanSet::parse("1-4095").is_err());
+        assert!(VlanSet::parse("5000").is_err());
+        assert!(VlanSet::parse("10-5").is_err());
+    }
+
+    #[test]
+    fn iter_yields_members_in_order() {
+        let set: VlanSet = "5,1,3".parse().unwrap();
+        let members: Vec<u16> = set.iter().map(|v| v.as_u16()).collect();
+        assert_eq!(members, vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let set: VlanSet = "0,1-100,4094".parse().unwrap();
+        assert_eq!(VlanSet::from_bytes(set.to_bytes()), set);
+    }
+
+    #[test]
+    fn full_and_complement_never_yield_reserved_vid_4095() {
+        assert!(VlanSet::FULL.iter().all(|v| v.as_u16() != 4095));
+        assert!(VlanSet::EMPTY.complement().iter().all(|v| v.as_u16() != 4095));
+
+        // A byte buffer with bit 4095 set (e.g. from an untrusted peer)
+        // must not resurrect it either.
+        let mut bytes = [0u8; VlanSet::BYTE_SIZE];
+        bytes[VlanSet::BYTE_SIZE - 1] = 0x80;
+        let set = VlanSet::from_bytes(bytes);
+        assert!(set.iter().all(|v| v.as_u16() != 4095));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{ParseError, TagControlInformation, VlanTag};
+
+/// The default cap on how many stacked tags [`VlanStack::try_from_bytes`]
+/// will parse before giving up and returning the rest of the buffer
+/// unparsed.
+pub const DEFAULT_MAX_DEPTH: usize = 2;
+
+/// A stack of one or more 802.1Q/802.1ad VLAN tags, as used by QinQ double
+/// tagging: an outer service tag (typically TPID [`crate::TPID_SERVICE`])
+/// wrapping an inner customer tag (TPID [`crate::TPID_CUSTOMER`]).
+///
+/// Tags are kept outermost-first, matching their order on the wire.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VlanStack {
+    tags: Vec<VlanTag>,
+}
+
+impl VlanStack {
+    /// Build a stack from already-parsed tags, outermost first.
+    pub fn new(tags: Vec<VlanTag>) -> Self {
+        Self { tags }
+    }
+
+    /// The outermost tag, i.e. the one closest to the source MAC address.
+    pub fn outer(&self) -> Option<&VlanTag> {
+        self.tags.first()
+    }
+
+    /// The innermost tag, i.e. the one closest to the payload EtherType.
+    pub fn inner(&self) -> Option<&VlanTag> {
+        self.tags.last()
+    }
+
+    /// How many tags are stacked.
+    pub fn depth(&self) -> usize {
+        self.tags.len()
+    }
+
+    /// The stacked tags, outermost first.
+    pub fn tags(&self) -> &[VlanTag] {
+        &self.tags
+    }
+
+    /// Iterate over the stacked tags, outermost first.
+    pub fn iter(&self) -> std::slice::Iter<'_, VlanTag> {
+        self.tags.iter()
+    }
+
+    /// Parse a stack of tags off the front of `bytes`, stopping at
+    /// [`DEFAULT_MAX_DEPTH`] tags.
+    ///
+    /// See [`Self::try_from_bytes_with_max_depth`] for the full semantics.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), ParseError> {
+        Self::try_from_bytes_with_max_depth(bytes, DEFAULT_MAX_DEPTH)
+    }
+
+    /// Parse a stack of tags off the front of `bytes`, reading at most
+    /// `max_depth` tags. The first tag is always read, so a `max_depth` of
+    /// 0 behaves like 1.
+    ///
+    /// A tag is considered stacked when the 2 bytes immediately following
+    /// its TCI are themselves a recognized TPID, in which case those same
+    /// bytes are reinterpreted as the next tag's TPID rather than consumed
+    /// as an EtherType. Parsing stops, and those trailing bytes are
+    /// consumed as the innermost tag's EtherType, as soon as they are not a
+    /// recognized TPID. If `max_depth` tags have been read and the next
+    /// bytes still look like a tag, parsing stops without consuming them
+    /// (and the innermost tag's `ether_type` field holds that unparsed
+    /// TPID rather than a real EtherType) so the caller can keep parsing
+    /// the remainder itself.
+    pub fn try_from_bytes_with_max_depth(
+        bytes: &[u8],
+        max_depth: usize,
+    ) -> Result<(Self, &[u8]), ParseError> {
+        let mut tags = Vec::new();
+        let mut rest = bytes;
+        loop {
+            if rest.len() < 4 {
+                return Err(ParseError::UnexpectedEof);
+            }
+            let tpid = u16::from_be_bytes([rest[0], rest[1]]);
+            if !VlanTag::is_known_tpid(tpid) {
+                return Err(ParseError::UnknownTpid(tpid));
+            }
+            let tci = TagControlInformation::from_be_bytes([rest[2], rest[3]])?;
+            if rest.len() < 6 {
+                return Err(ParseError::UnexpectedEof);
+            }
+            let next_field = u16::from_be_bytes([rest[4], rest[5]]);
+            tags.push(VlanTag {
+                tpid,
+                tci,
+                ether_type: next_field,
+            });
+            rest = &rest[4..];
+
+            if !VlanTag::is_known_tpid(next_field) {
+                rest = &rest[2..];
+                break;
+            }
+            if tags.len() >= max_depth {
+                break;
+            }
+        }
+        Ok((Self { tags }, rest))
+    }
+
+    /// Emit this stack back to its big-endian on-wire representation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.tags.len() * 4 + 2);
+        for tag in &self.tags {
+            buf.extend_from_slice(&tag.tpid.to_be_bytes());
+            buf.extend_from_slice(&tag.tci.to_be_bytes());
+        }
+        if let Some(last) = self.tags.last() {
+            buf.extend_from_slice(&last.ether_type.to_be_bytes());
+        }
+        buf
+    }
+}
+
+impl<'a> IntoIterator for &'a VlanStack {
+    type Item = &'a VlanTag;
+    type IntoIter = std::slice::Iter<'a, VlanTag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tags.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MaybeVlanId;
+
+    fn tag(tpid: u16, vid: u16, ether_type: u16) -> VlanTag {
+        VlanTag {
+            tpid,
+            tci: TagControlInformation::new(0, false, MaybeVlanId::try_new(vid).unwrap()),
+            ether_type,
+        }
+    }
+
+    #[test]
+    fn single_tag_stack() {
+        let stack = VlanStack::new(vec![tag(crate::TPID_CUSTOMER, 10, 0x0800)]);
+        let bytes = stack.to_bytes();
+        let (parsed, rest) = VlanStack::try_from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, stack);
+        assert!(rest.is_empty());
+        assert_eq!(parsed.depth(), 1);
+        assert_eq!(parsed.outer(), parsed.inner());
+    }
+
+    #[test]
+    fn qinq_stack() {
+        let stack = VlanStack::new(vec![
+            tag(crate::TPID_SERVICE, 100, crate::TPID_CUSTOMER),
+            tag(crate::TPID_CUSTOMER, 10, 0x0800),
+        ]);
+        let bytes = stack.to_bytes();
+        let (parsed, rest) = VlanStack::try_from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, stack);
+        assert!(rest.is_empty());
+        assert_eq!(parsed.depth(), 2);
+        assert_eq!(parsed.outer().unwrap().tpid, crate::TPID_SERVICE);
+        assert_eq!(parsed.inner().unwrap().tpid, crate::TPID_CUSTOMER);
+
+        let tags: Vec<_> = parsed.iter().collect();
+        assert_eq!(tags.len(), 2);
+    }
+
+    #[test]
+    fn depth_cap_leaves_remainder_unparsed() {
+        let stack = VlanStack::new(vec![
+            tag(crate::TPID_SERVICE, 100, crate::TPID_CUSTOMER),
+            tag(crate::TPID_CUSTOMER, 10, crate::TPID_CUSTOMER),
+            tag(crate::TPID_CUSTOMER, 20, 0x0800),
+        ]);
+        let bytes = stack.to_bytes();
+
+        let (parsed, rest) = VlanStack::try_from_bytes_with_max_depth(&bytes, 2).unwrap();
+        assert_eq!(parsed.depth(), 2);
+        // The remaining bytes are the third tag's TPID + TCI + EtherType,
+        // left untouched since we stopped before descending into it.
+        assert_eq!(rest.len(), 6);
+        let (third, rest) = VlanTag::read_from(rest).unwrap();
+        assert_eq!(third.tci.vid(), MaybeVlanId::try_new(20).unwrap());
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_inner_vid() {
+        // Outer tag is valid, inner TCI carries VID 4095 (reserved).
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&crate::TPID_SERVICE.to_be_bytes());
+        bytes.extend_from_slice(&TagControlInformation::new(0, false, MaybeVlanId::NATIVE).to_be_bytes());
+        bytes.extend_from_slice(&crate::TPID_CUSTOMER.to_be_bytes());
+        bytes.extend_from_slice(&[0x0F, 0xFF]);
+        bytes.extend_from_slice(&0x0800u16.to_be_bytes());
+
+        assert!(VlanStack::try_from_bytes(&bytes).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt::{self, Display};
+
+use crate::{InvalidVlanId, TagControlInformation};
+
+/// TPID of a customer (802.1Q) tag.
+pub const TPID_CUSTOMER: u16 = 0x8100;
+
+/// TPID of a service (802.1ad) tag, as used by most vendors.
+pub const TPID_SERVICE: u16 = 0x88A8;
+
+/// TPID of a service (802.1ad) tag, as used by some legacy vendors.
+pub const TPID_SERVICE_LEGACY: u16 = 0x9100;
+
+/// The error value returned when reading a [`VlanTag`] off a byte buffer fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// Fewer bytes remained in the buffer than a tag requires.
+    UnexpectedEof,
+
+    /// The leading 16 bits were not a recognized 802.1Q/802.1ad TPID.
+    UnknownTpid(u16),
+
+    /// The VID embedded in the TCI was out of range.
+    InvalidVlanId(InvalidVlanId),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEof => f.write_str("unexpected end of buffer while reading VLAN tag"),
+            Self::UnknownTpid(tpid) => write!(f, "unrecognized TPID: {tpid:#06x}"),
+            Self::InvalidVlanId(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<InvalidVlanId> for ParseError {
+    fn from(value: InvalidVlanId) -> Self {
+        Self::InvalidVlanId(value)
+    }
+}
+
+/// A single 802.1Q or 802.1ad VLAN tag, together with the EtherType that
+/// follows it.
+///
+/// This is the on-wire unit that sits between the source MAC address (or a
+/// preceding tag) and the next EtherType in an Ethernet frame: a 2-byte
+/// TPID, the 2-byte [`TagControlInformation`], and the 2-byte inner
+/// EtherType.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VlanTag {
+    /// Tag Protocol Identifier, e.g. [`TPID_CUSTOMER`].
+    pub tpid: u16,
+
+    /// Tag Control Information (PCP/DEI/VID).
+    pub tci: TagControlInformation,
+
+    /// The EtherType (or next TPID) following this tag.
+    pub ether_type: u16,
+}
+
+impl VlanTag {
+    /// The size of a tag on the wire, in octets.
+    pub const OCTET_SIZE: usize = 6;
+
+    /// Whether `tpid` is a TPID this crate recognizes as introducing a
+    /// VLAN tag.
+    pub const fn is_known_tpid(tpid: u16) -> bool {
+        matches!(tpid, TPID_CUSTOMER | TPID_SERVICE | TPID_SERVICE_LEGACY)
+    }
+
+    /// Read a tag off the front of `bytes`, returning it along with the
+    /// remaining slice.
+    pub fn read_from(bytes: &[u8]) -> Result<(Self, &[u8]), ParseError> {
+        if bytes.len() < Self::OCTET_SIZE {
+            return Err(ParseError::UnexpectedEof);
+        }
+        let tpid = u16::from_be_bytes([bytes[0], bytes[1]]);
+        if !Self::is_known_tpid(tpid) {
+            return Err(ParseError::UnknownTpid(tpid));
+        }
+        let tci = TagControlInformation::from_be_bytes([bytes[2], bytes[3]])?;
+        let ether_type = u16::from_be_bytes([bytes[4], bytes[5]]);
+        Ok((
+            Self {
+                tpid,
+                tci,
+                ether_type,
+            },
+            &bytes[Self::OCTET_SIZE..],
+        ))
+    }
+
+    /// Write this tag to `writer` in its big-endian on-wire representation.
+    pub fn write_to(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(&self.tpid.to_be_bytes())?;
+        writer.write_all(&self.tci.to_be_bytes())?;
+        writer.write_all(&self.ether_type.to_be_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MaybeVlanId;
+
+    #[test]
+    fn round_trip() {
+        let tag = VlanTag {
+            tpid: TPID_CUSTOMER,
+            tci: TagControlInformation::new(3, false, MaybeVlanId::try_new(42).unwrap()),
+            ether_type: 0x0800,
+        };
+        let mut buf = Vec::new();
+        tag.write_to(&mut buf).unwrap();
+        assert_eq!(buf.len(), VlanTag::OCTET_SIZE);
+
+        let (parsed, rest) = VlanTag::read_from(&buf).unwrap();
+        assert_eq!(parsed, tag);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn read_from_leaves_trailing_bytes() {
+        let buf = [0x81, 0x00, 0x00, 0x2a, 0x08, 0x00, 0xAA, 0xBB];
+        let (_, rest) = VlanTag::read_from(&buf).unwrap();
+        assert_eq!(rest, &[0xAA, 0xBB]);
+    }
+
+    #[test]
+    fn rejects_unknown_tpid() {
+        let buf = [0x08, 0x00, 0x00, 0x00, 0x00, 0x00];
+        assert_eq!(
+            VlanTag::read_from(&buf),
+            Err(ParseError::UnknownTpid(0x0800))
+        );
+    }
+
+    #[test]
+    fn rejects_short_buffer() {
+        let buf = [0x81, 0x00, 0x00];
+        assert_eq!(VlanTag::read_from(&buf), Err(ParseError::UnexpectedEof));
+    }
+}